@@ -1,8 +1,140 @@
-use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, Local};
 use serde::{Deserialize, Serialize};
 
 use super::Entity;
 
+/// A language a recipe can be expressed in. The default language is chosen
+/// per recipe and drives both the translation fallback and the language of
+/// validation errors; ingredient-list parse errors take a `Lang` too. Unit
+/// conversion errors and ISO-8601 duration parse errors are always in
+/// Portuguese.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Lang {
+    Pt,
+    En,
+    Es,
+}
+
+/// The recipe fields that carry a non-empty invariant, used to look up a
+/// validation message in the recipe's default language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValidationField {
+    Name,
+    Tags,
+    Ingredients,
+    Instructions,
+}
+
+impl ValidationField {
+    fn message(self, lang: Lang) -> &'static str {
+        match (lang, self) {
+            (Lang::Pt, ValidationField::Name) => "A receita precisa ter um nome",
+            (Lang::Pt, ValidationField::Tags) => "A receita precisa pelo menos de uma tag",
+            (Lang::Pt, ValidationField::Ingredients) => {
+                "A receita precisa pelo menos de um ingrediente"
+            }
+            (Lang::Pt, ValidationField::Instructions) => {
+                "A receita precisa pelo menos de uma instrução"
+            }
+            (Lang::En, ValidationField::Name) => "A recipe needs a name",
+            (Lang::En, ValidationField::Tags) => "A recipe needs at least one tag",
+            (Lang::En, ValidationField::Ingredients) => "A recipe needs at least one ingredient",
+            (Lang::En, ValidationField::Instructions) => "A recipe needs at least one instruction",
+            (Lang::Es, ValidationField::Name) => "La receta necesita un nombre",
+            (Lang::Es, ValidationField::Tags) => "La receta necesita al menos una etiqueta",
+            (Lang::Es, ValidationField::Ingredients) => {
+                "La receta necesita al menos un ingrediente"
+            }
+            (Lang::Es, ValidationField::Instructions) => {
+                "La receta necesita al menos una instrucción"
+            }
+        }
+    }
+}
+
+/// Serde glue for (de)serialising an optional [`chrono::Duration`] as an
+/// ISO-8601 duration string (e.g. `"PT1H30M"`), the shape used by
+/// schema.org/Recipe JSON-LD. Missing values map to `None`.
+mod iso8601_duration {
+    use super::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(duration) => serializer.serialize_some(&to_iso8601(duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(raw) => from_iso8601(&raw).map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+
+    fn to_iso8601(duration: &Duration) -> String {
+        let total = duration.num_seconds();
+        let hours = total / 3600;
+        let minutes = (total % 3600) / 60;
+        let seconds = total % 60;
+
+        let mut out = String::from("PT");
+        if hours != 0 {
+            out.push_str(&format!("{hours}H"));
+        }
+        if minutes != 0 {
+            out.push_str(&format!("{minutes}M"));
+        }
+        if seconds != 0 || (hours == 0 && minutes == 0) {
+            out.push_str(&format!("{seconds}S"));
+        }
+        out
+    }
+
+    fn from_iso8601(raw: &str) -> Result<Duration, String> {
+        let body = raw
+            .trim()
+            .strip_prefix('P')
+            .ok_or_else(|| format!("duração ISO-8601 inválida: {raw}"))?;
+        let (date_part, time_part) = match body.split_once('T') {
+            Some((date, time)) => (date, time),
+            None => (body, ""),
+        };
+
+        let seconds = component(date_part, 'D')? * 86_400
+            + component(time_part, 'H')? * 3_600
+            + component(time_part, 'M')? * 60
+            + component(time_part, 'S')?;
+
+        Ok(Duration::seconds(seconds))
+    }
+
+    fn component(segment: &str, designator: char) -> Result<i64, String> {
+        match segment.find(designator) {
+            Some(pos) => {
+                let start = segment[..pos]
+                    .rfind(|c: char| !c.is_ascii_digit())
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                segment[start..pos]
+                    .parse::<i64>()
+                    .map_err(|_| format!("componente '{designator}' inválido em {segment:?}"))
+            }
+            None => Ok(0),
+        }
+    }
+}
+
 impl Entity for Recipe {}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,23 +159,65 @@ impl TryFrom<String> for RecipeId {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct RecipeName(String);
+#[serde(try_from = "RawRecipeName")]
+pub struct RecipeName {
+    default: Lang,
+    translations: HashMap<Lang, String>,
+}
 
-impl RecipeName {
-    pub fn value(&self) -> &String {
-        &self.0
-    }
+/// Wire shape for [`RecipeName`]. Deserialising goes through this so the
+/// "default translation must be present" invariant is re-checked instead of
+/// panicking later on a missing key.
+#[derive(Deserialize)]
+struct RawRecipeName {
+    default: Lang,
+    translations: HashMap<Lang, String>,
 }
 
-impl TryFrom<String> for RecipeName {
-    type Error = &'static str;
+impl TryFrom<RawRecipeName> for RecipeName {
+    type Error = String;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
+    fn try_from(raw: RawRecipeName) -> Result<Self, Self::Error> {
+        match raw.translations.get(&raw.default) {
+            Some(value) if !value.is_empty() => Ok(RecipeName {
+                default: raw.default,
+                translations: raw.translations,
+            }),
+            _ => Err(ValidationField::Name.message(raw.default).to_string()),
+        }
+    }
+}
+
+impl RecipeName {
+    /// Builds a name whose default language is `default`. The default
+    /// translation must be present and non-empty; that is the invariant
+    /// replacing the old single-string check.
+    pub fn new(default: Lang, value: String) -> Result<Self, String> {
         if value.is_empty() {
-            Err("A receita precisa ter um nome")
-        } else {
-            Ok(RecipeName(value))
+            return Err(ValidationField::Name.message(default).to_string());
         }
+        let mut translations = HashMap::new();
+        translations.insert(default, value);
+        Ok(RecipeName {
+            default,
+            translations,
+        })
+    }
+
+    /// Adds or replaces the translation for `lang`.
+    pub fn insert(&mut self, lang: Lang, value: String) {
+        self.translations.insert(lang, value);
+    }
+
+    /// The name in `lang`, falling back to the default language.
+    pub fn in_lang(&self, lang: Lang) -> &str {
+        self.translations
+            .get(&lang)
+            .unwrap_or_else(|| &self.translations[&self.default])
+    }
+
+    pub fn value(&self) -> &String {
+        &self.translations[&self.default]
     }
 }
 
@@ -51,62 +225,292 @@ impl TryFrom<String> for RecipeName {
 pub struct RecipeTags(Vec<String>);
 
 impl RecipeTags {
+    pub fn new(lang: Lang, value: Vec<String>) -> Result<Self, String> {
+        if value.is_empty() {
+            Err(ValidationField::Tags.message(lang).to_string())
+        } else {
+            Ok(RecipeTags(value))
+        }
+    }
+
     pub fn value(&self) -> &Vec<String> {
         &self.0
     }
 }
 
-impl TryFrom<Vec<String>> for RecipeTags {
-    type Error = &'static str;
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IngredientUnit {
+    Gram,
+    KiloGram,
+    MilliLiter,
+    Liter,
+    Count,
+    Unitless,
+}
 
-    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-        if value.is_empty() {
-            Err("A receita precisa pelo menos de uma tag")
-        } else {
-            Ok(RecipeTags(value))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnitFamily {
+    Mass,
+    Volume,
+}
+
+impl IngredientUnit {
+    /// The measurement family a unit belongs to, or `None` for plain counts.
+    fn family(&self) -> Option<UnitFamily> {
+        match self {
+            IngredientUnit::Gram | IngredientUnit::KiloGram => Some(UnitFamily::Mass),
+            IngredientUnit::MilliLiter | IngredientUnit::Liter => Some(UnitFamily::Volume),
+            IngredientUnit::Count | IngredientUnit::Unitless => None,
+        }
+    }
+
+    /// How many base units (g for mass, ml for volume) one of this unit holds.
+    fn base_factor(&self) -> f64 {
+        match self {
+            IngredientUnit::KiloGram | IngredientUnit::Liter => 1000.0,
+            _ => 1.0,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct RecipeIngredients(Vec<String>);
+pub struct Ingredient {
+    pub name: String,
+    pub quantity: f64,
+    pub unit: IngredientUnit,
+}
 
-impl RecipeIngredients {
-    pub fn value(&self) -> &Vec<String> {
-        &self.0
+impl Ingredient {
+    pub fn new(name: String, quantity: f64, unit: IngredientUnit) -> Self {
+        Ingredient {
+            name,
+            quantity,
+            unit,
+        }
+    }
+
+    /// Converts this ingredient to another unit within the same family
+    /// (kg↔g, l↔ml). Counts and unitless quantities cannot be converted.
+    pub fn convert_to(&self, unit: IngredientUnit) -> Result<Ingredient, &'static str> {
+        if self.unit == unit {
+            return Ok(self.clone());
+        }
+        match (self.unit.family(), unit.family()) {
+            (Some(from), Some(to)) if from == to => Ok(Ingredient {
+                name: self.name.clone(),
+                quantity: self.quantity * self.unit.base_factor() / unit.base_factor(),
+                unit,
+            }),
+            _ => Err("Não é possível converter entre unidades de famílias diferentes"),
+        }
+    }
+
+    /// Multiplies the quantity by `factor`, preserving the unit unchanged.
+    /// Counts scale like any other quantity and stay fractional.
+    fn scaled(&self, factor: f64) -> Ingredient {
+        Ingredient {
+            name: self.name.clone(),
+            quantity: self.quantity * factor,
+            unit: self.unit,
+        }
     }
 }
 
-impl TryFrom<Vec<String>> for RecipeIngredients {
-    type Error = &'static str;
+/// A failure encountered while parsing a free-text ingredient list,
+/// pointing at the offending comma-separated segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseIngredientError {
+    pub segment: usize,
+    pub reason: String,
+}
 
-    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-        if value.is_empty() {
-            Err("A receita precisa pelo menos de um ingrediente")
+/// The "empty segment" parse message in the given language, so the parser
+/// surfaces errors in the caller's language rather than hardcoding one.
+fn empty_segment_message(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Pt => "segmento vazio",
+        Lang::En => "empty segment",
+        Lang::Es => "segmento vacío",
+    }
+}
+
+/// Matches a unit abbreviation, returning the `IngredientUnit` it maps to
+/// together with a multiplier applied to the parsed quantity (spoon
+/// measures are normalised to millilitres).
+fn match_unit(token: &str) -> Option<(IngredientUnit, f64)> {
+    match token.to_lowercase().as_str() {
+        "g" | "gram" | "grams" => Some((IngredientUnit::Gram, 1.0)),
+        "kg" => Some((IngredientUnit::KiloGram, 1.0)),
+        "ml" => Some((IngredientUnit::MilliLiter, 1.0)),
+        "l" => Some((IngredientUnit::Liter, 1.0)),
+        "tsp" => Some((IngredientUnit::MilliLiter, 5.0)),
+        "tbsp" => Some((IngredientUnit::MilliLiter, 15.0)),
+        _ => None,
+    }
+}
+
+/// The decimal value of a common unicode vulgar fraction.
+fn fraction_value(c: char) -> Option<f64> {
+    match c {
+        '¼' => Some(0.25),
+        '½' => Some(0.5),
+        '¾' => Some(0.75),
+        '⅓' => Some(1.0 / 3.0),
+        '⅔' => Some(2.0 / 3.0),
+        _ => None,
+    }
+}
+
+/// Scans a leading numeric quantity (a decimal, an optional trailing
+/// unicode fraction, or both) and returns it alongside the unparsed rest.
+fn scan_quantity(segment: &str) -> Option<(f64, &str)> {
+    let mut cursor = 0;
+    let mut number = String::new();
+    for (i, c) in segment.char_indices() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            cursor = i + c.len_utf8();
         } else {
-            Ok(RecipeIngredients(value))
+            break;
+        }
+    }
+
+    let mut value = number.parse::<f64>().ok();
+
+    let remainder = &segment[cursor..];
+    let trimmed = remainder.trim_start();
+    if let Some(first) = trimmed.chars().next()
+        && let Some(frac) = fraction_value(first)
+    {
+        value = Some(value.unwrap_or(0.0) + frac);
+        cursor += (remainder.len() - trimmed.len()) + first.len_utf8();
+    }
+
+    value.map(|v| (v, &segment[cursor..]))
+}
+
+/// Parses a single trimmed, non-empty segment into an `Ingredient`.
+fn parse_segment(segment: &str) -> Ingredient {
+    match scan_quantity(segment) {
+        Some((quantity, rest)) => {
+            let rest = rest.trim_start();
+            let (unit, multiplier, name) = match rest.split_once(char::is_whitespace) {
+                Some((candidate, tail)) => match match_unit(candidate) {
+                    Some((unit, mult)) => (unit, mult, tail.trim().to_string()),
+                    None => (IngredientUnit::Unitless, 1.0, rest.to_string()),
+                },
+                None => match match_unit(rest) {
+                    Some((unit, mult)) => (unit, mult, String::new()),
+                    None => (IngredientUnit::Unitless, 1.0, rest.to_string()),
+                },
+            };
+            Ingredient::new(name, quantity * multiplier, unit)
         }
+        None => Ingredient::new(segment.to_string(), 1.0, IngredientUnit::Unitless),
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct RecipeInstructions(Vec<String>);
+pub struct RecipeIngredients(Vec<Ingredient>);
 
-impl RecipeInstructions {
-    pub fn value(&self) -> &Vec<String> {
+impl RecipeIngredients {
+    pub fn new(lang: Lang, value: Vec<Ingredient>) -> Result<Self, String> {
+        if value.is_empty() {
+            Err(ValidationField::Ingredients.message(lang).to_string())
+        } else {
+            Ok(RecipeIngredients(value))
+        }
+    }
+
+    pub fn value(&self) -> &Vec<Ingredient> {
         &self.0
     }
+
+    /// Parses a comma-separated shopping-list string such as
+    /// `"135g plain flour, 1 tsp baking powder, 2 large eggs, 130ml milk"`
+    /// into structured ingredients. Each segment contributes one
+    /// `Ingredient`; a segment that is empty yields a
+    /// [`ParseIngredientError`] carrying its index rather than being dropped.
+    /// Error messages are emitted in `lang`, matching the language selection
+    /// used by the rest of the crate.
+    pub fn from_input_string(lang: Lang, input: &str) -> Result<Self, ParseIngredientError> {
+        let mut ingredients = Vec::new();
+        for (index, raw) in input.split(',').enumerate() {
+            let segment = raw.trim();
+            if segment.is_empty() {
+                return Err(ParseIngredientError {
+                    segment: index,
+                    reason: empty_segment_message(lang).to_string(),
+                });
+            }
+            ingredients.push(parse_segment(segment));
+        }
+
+        RecipeIngredients::new(lang, ingredients).map_err(|reason| ParseIngredientError {
+            segment: 0,
+            reason,
+        })
+    }
 }
 
-impl TryFrom<Vec<String>> for RecipeInstructions {
-    type Error = &'static str;
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "RawRecipeInstructions")]
+pub struct RecipeInstructions {
+    default: Lang,
+    translations: HashMap<Lang, Vec<String>>,
+}
+
+/// Wire shape for [`RecipeInstructions`], re-checking the default-translation
+/// invariant on deserialisation (see [`RawRecipeName`]).
+#[derive(Deserialize)]
+struct RawRecipeInstructions {
+    default: Lang,
+    translations: HashMap<Lang, Vec<String>>,
+}
+
+impl TryFrom<RawRecipeInstructions> for RecipeInstructions {
+    type Error = String;
+
+    fn try_from(raw: RawRecipeInstructions) -> Result<Self, Self::Error> {
+        match raw.translations.get(&raw.default) {
+            Some(value) if !value.is_empty() => Ok(RecipeInstructions {
+                default: raw.default,
+                translations: raw.translations,
+            }),
+            _ => Err(ValidationField::Instructions.message(raw.default).to_string()),
+        }
+    }
+}
 
-    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+impl RecipeInstructions {
+    /// Builds instructions whose default language is `default`. The default
+    /// translation must be present and non-empty.
+    pub fn new(default: Lang, value: Vec<String>) -> Result<Self, String> {
         if value.is_empty() {
-            Err("A receita precisa pelo menos de uma instrução")
-        } else {
-            Ok(RecipeInstructions(value))
+            return Err(ValidationField::Instructions.message(default).to_string());
         }
+        let mut translations = HashMap::new();
+        translations.insert(default, value);
+        Ok(RecipeInstructions {
+            default,
+            translations,
+        })
+    }
+
+    /// Adds or replaces the translation for `lang`.
+    pub fn insert(&mut self, lang: Lang, value: Vec<String>) {
+        self.translations.insert(lang, value);
+    }
+
+    /// The instructions in `lang`, falling back to the default language.
+    pub fn in_lang(&self, lang: Lang) -> &[String] {
+        self.translations
+            .get(&lang)
+            .unwrap_or_else(|| &self.translations[&self.default])
+    }
+
+    pub fn value(&self) -> &Vec<String> {
+        &self.translations[&self.default]
     }
 }
 
@@ -118,21 +522,38 @@ pub struct Recipe {
     pub ingredients: RecipeIngredients,
     pub instructions: RecipeInstructions,
     pub published_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, with = "iso8601_duration")]
+    pub prep_time: Option<Duration>,
+    #[serde(default, with = "iso8601_duration")]
+    pub cook_time: Option<Duration>,
+    #[serde(default, with = "iso8601_duration")]
+    pub total_time: Option<Duration>,
+    #[serde(default)]
+    pub recipe_yield: Option<u32>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub components: Vec<RecipeId>,
 }
 
 impl Recipe {
     pub fn new(
         id: String,
+        lang: Lang,
         name: String,
         tags: Vec<String>,
-        ingredients: Vec<String>,
+        ingredients: Vec<Ingredient>,
         instructions: Vec<String>,
     ) -> Result<Self, String> {
         let recipe_id = RecipeId::try_from(id)?;
-        let recipe_name = RecipeName::try_from(name)?;
-        let recipe_tags = RecipeTags::try_from(tags)?;
-        let recipe_ingredients = RecipeIngredients::try_from(ingredients)?;
-        let recipe_instructions = RecipeInstructions::try_from(instructions)?;
+        let recipe_name = RecipeName::new(lang, name)?;
+        let recipe_tags = RecipeTags::new(lang, tags)?;
+        let recipe_ingredients = RecipeIngredients::new(lang, ingredients)?;
+        let recipe_instructions = RecipeInstructions::new(lang, instructions)?;
 
         Ok(Recipe {
             id: recipe_id,
@@ -141,6 +562,14 @@ impl Recipe {
             ingredients: recipe_ingredients,
             instructions: recipe_instructions,
             published_at: Some(Local::now()),
+            description: None,
+            prep_time: None,
+            cook_time: None,
+            total_time: None,
+            recipe_yield: None,
+            category: None,
+            image_url: None,
+            components: Vec::new(),
         })
     }
 
@@ -163,6 +592,132 @@ impl Recipe {
     pub fn instructions(&self) -> &RecipeInstructions {
         &self.instructions
     }
+
+    /// The recipe name in `lang`, falling back to the default language when
+    /// no translation exists.
+    pub fn name_in(&self, lang: Lang) -> &str {
+        self.name.in_lang(lang)
+    }
+
+    /// The recipe instructions in `lang`, falling back to the default
+    /// language when no translation exists.
+    pub fn instructions_in(&self, lang: Lang) -> &[String] {
+        self.instructions.in_lang(lang)
+    }
+
+    /// Reports whether the declared times line up: when `prep_time`,
+    /// `cook_time` and `total_time` are all present, `total_time` should be
+    /// at least the sum of the other two. This is surfaced rather than
+    /// enforced, since real-world JSON-LD data is often loosely rounded.
+    pub fn time_is_consistent(&self) -> bool {
+        match (self.prep_time, self.cook_time, self.total_time) {
+            (Some(prep), Some(cook), Some(total)) => total >= prep + cook,
+            _ => true,
+        }
+    }
+
+    /// Resizes the recipe by multiplying every ingredient quantity by
+    /// `factor` (e.g. `2.0` doubles the yield). Units are left untouched,
+    /// so counts such as "2 eggs" simply become "4 eggs".
+    pub fn scale(&mut self, factor: f64) {
+        for ingredient in self.ingredients.0.iter_mut() {
+            *ingredient = ingredient.scaled(factor);
+        }
+    }
+
+    /// Recursively resolves this recipe's components against `book` and
+    /// returns the merged ingredient list, summing quantities that share
+    /// the same name and unit. Unknown references and reference cycles are
+    /// reported as a [`ResolveError`].
+    pub fn flattened_ingredients(
+        &self,
+        book: &RecipeBook,
+    ) -> Result<Vec<Ingredient>, ResolveError> {
+        let mut merged = Vec::new();
+        let mut visiting = Vec::new();
+        self.walk_components(book, &mut merged, &mut visiting)?;
+        Ok(merged)
+    }
+
+    fn walk_components(
+        &self,
+        book: &RecipeBook,
+        merged: &mut Vec<Ingredient>,
+        visiting: &mut Vec<String>,
+    ) -> Result<(), ResolveError> {
+        let key = self.id.value().clone().unwrap_or_default();
+        if visiting.contains(&key) {
+            let mut path = visiting.clone();
+            path.push(key);
+            return Err(ResolveError::Cycle(path));
+        }
+
+        visiting.push(key);
+        for ingredient in self.ingredients.value() {
+            merge_ingredient(merged, ingredient);
+        }
+        for component in &self.components {
+            let child = book.resolve(component)?;
+            child.walk_components(book, merged, visiting)?;
+        }
+        visiting.pop();
+        Ok(())
+    }
+}
+
+/// Sums `ingredient` into `merged`, combining with an existing entry that
+/// shares the same name and unit.
+fn merge_ingredient(merged: &mut Vec<Ingredient>, ingredient: &Ingredient) {
+    match merged
+        .iter_mut()
+        .find(|existing| existing.name == ingredient.name && existing.unit == ingredient.unit)
+    {
+        Some(existing) => existing.quantity += ingredient.quantity,
+        None => merged.push(ingredient.clone()),
+    }
+}
+
+/// A failure while resolving sub-recipe references.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// A referenced recipe id was not present in the book.
+    UnknownReference(String),
+    /// A reference cycle was detected; the vector names the path that
+    /// closes the loop.
+    Cycle(Vec<String>),
+}
+
+/// A flat collection of recipes that resolves [`RecipeId`] references into
+/// shared [`Rc<Recipe>`] handles.
+#[derive(Debug, Clone, Default)]
+pub struct RecipeBook {
+    recipes: HashMap<String, Rc<Recipe>>,
+}
+
+impl RecipeBook {
+    /// Indexes `recipes` by their id. Recipes without an id cannot be
+    /// referenced as components and are skipped.
+    pub fn new(recipes: Vec<Recipe>) -> Self {
+        let mut book = HashMap::new();
+        for recipe in recipes {
+            if let Some(id) = recipe.id.value().clone() {
+                book.insert(id, Rc::new(recipe));
+            }
+        }
+        RecipeBook { recipes: book }
+    }
+
+    /// Resolves a single reference into its concrete recipe.
+    pub fn resolve(&self, id: &RecipeId) -> Result<Rc<Recipe>, ResolveError> {
+        match id.value() {
+            Some(key) => self
+                .recipes
+                .get(key)
+                .cloned()
+                .ok_or_else(|| ResolveError::UnknownReference(key.clone())),
+            None => Err(ResolveError::UnknownReference(String::new())),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -172,12 +727,17 @@ mod tests {
     #[test]
     fn should_create_the_expected_recipe() {
         let tags = vec!["main".to_string(), "chicken".to_string()];
-        let ingredients = vec!["4 (6 to 7-ounce) boneless skinless chicken breasts\r".to_string()];
+        let ingredients = vec![Ingredient::new(
+            "boneless skinless chicken breasts".to_string(),
+            4.0,
+            IngredientUnit::Count,
+        )];
         let instructions = vec!["To marinate the chicken: In a non-reactive dish, combine the lemon juice, olive oil, oregano, salt, and pepper and mix together".to_string()];
         let name: &str = "Oregano Marinated Chicken";
 
         let new_recipe = Recipe::new(
             "10".to_string(),
+            Lang::Pt,
             name.to_string(),
             tags.clone(),
             ingredients.clone(),
@@ -196,12 +756,17 @@ mod tests {
     #[test]
     fn should_fail_without_a_name_or_ingredients_or_tags_or_instructions() {
         let tags = vec!["main".to_string(), "chicken".to_string()];
-        let ingredients = vec!["4 (6 to 7-ounce) boneless skinless chicken breasts\r".to_string()];
+        let ingredients = vec![Ingredient::new(
+            "boneless skinless chicken breasts".to_string(),
+            4.0,
+            IngredientUnit::Count,
+        )];
         let instructions = vec!["To marinate the chicken: In a non-reactive dish, combine the lemon juice, olive oil, oregano, salt, and pepper and mix together".to_string()];
         let name = "Oregano Marinated Chicken";
 
         let err_recipe = Recipe::new(
             "10".to_string(),
+            Lang::Pt,
             "".to_string(),
             tags.clone(),
             ingredients.clone(),
@@ -212,6 +777,7 @@ mod tests {
 
         let err_recipe = Recipe::new(
             "10".to_string(),
+            Lang::Pt,
             name.to_string(),
             vec![],
             ingredients.clone(),
@@ -225,6 +791,7 @@ mod tests {
 
         let err_recipe = Recipe::new(
             "10".to_string(),
+            Lang::Pt,
             name.to_string(),
             tags.clone(),
             vec![],
@@ -238,6 +805,7 @@ mod tests {
 
         let err_recipe = Recipe::new(
             "10".to_string(),
+            Lang::Pt,
             name.to_string(),
             tags.clone(),
             ingredients.clone(),
@@ -249,4 +817,294 @@ mod tests {
             "A receita precisa pelo menos de uma instrução"
         );
     }
+
+    #[test]
+    fn should_convert_within_the_same_unit_family() {
+        let flour = Ingredient::new("flour".to_string(), 1.5, IngredientUnit::KiloGram);
+        let in_grams = flour.convert_to(IngredientUnit::Gram).unwrap();
+        assert_eq!(in_grams.quantity, 1500.0);
+        assert_eq!(in_grams.unit, IngredientUnit::Gram);
+
+        let milk = Ingredient::new("milk".to_string(), 250.0, IngredientUnit::MilliLiter);
+        assert_eq!(milk.convert_to(IngredientUnit::Liter).unwrap().quantity, 0.25);
+    }
+
+    #[test]
+    fn should_not_convert_counts_or_across_families() {
+        let eggs = Ingredient::new("eggs".to_string(), 2.0, IngredientUnit::Count);
+        assert!(eggs.convert_to(IngredientUnit::Gram).is_err());
+
+        let flour = Ingredient::new("flour".to_string(), 100.0, IngredientUnit::Gram);
+        assert!(flour.convert_to(IngredientUnit::MilliLiter).is_err());
+    }
+
+    #[test]
+    fn should_scale_every_ingredient_quantity() {
+        let ingredients = vec![
+            Ingredient::new("flour".to_string(), 135.0, IngredientUnit::Gram),
+            Ingredient::new("eggs".to_string(), 3.0, IngredientUnit::Count),
+        ];
+        let mut recipe = Recipe::new(
+            "10".to_string(),
+            Lang::Pt,
+            "Pancakes".to_string(),
+            vec!["breakfast".to_string()],
+            ingredients,
+            vec!["Mix everything".to_string()],
+        )
+        .unwrap();
+
+        recipe.scale(0.5);
+
+        assert_eq!(recipe.ingredients.value()[0].quantity, 67.5);
+        assert_eq!(recipe.ingredients.value()[1].quantity, 1.5);
+        assert_eq!(recipe.ingredients.value()[1].unit, IngredientUnit::Count);
+    }
+
+    #[test]
+    fn should_parse_a_free_text_ingredient_list() {
+        let parsed =
+            RecipeIngredients::from_input_string(Lang::En, "135g plain flour, 1 tsp baking powder, 2 large eggs, 130ml milk")
+                .unwrap();
+        let items = parsed.value();
+
+        assert_eq!(items.len(), 4);
+
+        assert_eq!(items[0], Ingredient::new("plain flour".to_string(), 135.0, IngredientUnit::Gram));
+        // "tsp" normalises to 5 ml per spoon.
+        assert_eq!(items[1], Ingredient::new("baking powder".to_string(), 5.0, IngredientUnit::MilliLiter));
+        assert_eq!(items[2], Ingredient::new("large eggs".to_string(), 2.0, IngredientUnit::Unitless));
+        assert_eq!(items[3], Ingredient::new("milk".to_string(), 130.0, IngredientUnit::MilliLiter));
+    }
+
+    #[test]
+    fn should_parse_unicode_fractions_and_fall_back_to_unitless() {
+        let parsed = RecipeIngredients::from_input_string(Lang::En, "½ lemon, salt").unwrap();
+        let items = parsed.value();
+
+        assert_eq!(items[0].quantity, 0.5);
+        assert_eq!(items[0].unit, IngredientUnit::Unitless);
+        assert_eq!(items[1], Ingredient::new("salt".to_string(), 1.0, IngredientUnit::Unitless));
+    }
+
+    #[test]
+    fn should_report_the_index_of_an_empty_segment() {
+        let err = RecipeIngredients::from_input_string(Lang::En, "flour, , eggs").unwrap_err();
+        assert_eq!(err.segment, 1);
+        assert_eq!(err.reason, "empty segment");
+    }
+
+    #[test]
+    fn should_round_trip_iso8601_durations_through_serde() {
+        let mut recipe = Recipe::new(
+            "10".to_string(),
+            Lang::Pt,
+            "Cake".to_string(),
+            vec!["dessert".to_string()],
+            vec![Ingredient::new("flour".to_string(), 200.0, IngredientUnit::Gram)],
+            vec!["Bake".to_string()],
+        )
+        .unwrap();
+        recipe.prep_time = Some(Duration::minutes(20));
+        recipe.cook_time = Some(Duration::minutes(70));
+        recipe.total_time = Some(Duration::minutes(90));
+
+        let json = serde_json::to_string(&recipe).unwrap();
+        assert!(json.contains("\"PT20M\""));
+        assert!(json.contains("\"PT1H10M\""));
+
+        let back: Recipe = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.cook_time, Some(Duration::minutes(70)));
+        assert!(back.time_is_consistent());
+    }
+
+    #[test]
+    fn should_flag_inconsistent_times() {
+        let mut recipe = Recipe::new(
+            "10".to_string(),
+            Lang::Pt,
+            "Cake".to_string(),
+            vec!["dessert".to_string()],
+            vec![Ingredient::new("flour".to_string(), 200.0, IngredientUnit::Gram)],
+            vec!["Bake".to_string()],
+        )
+        .unwrap();
+        recipe.prep_time = Some(Duration::minutes(20));
+        recipe.cook_time = Some(Duration::minutes(70));
+        recipe.total_time = Some(Duration::minutes(60));
+
+        assert!(!recipe.time_is_consistent());
+    }
+
+    #[test]
+    fn should_return_translations_and_fall_back_to_the_default_language() {
+        let mut recipe = Recipe::new(
+            "10".to_string(),
+            Lang::En,
+            "Pancakes".to_string(),
+            vec!["breakfast".to_string()],
+            vec![Ingredient::new("flour".to_string(), 135.0, IngredientUnit::Gram)],
+            vec!["Mix everything".to_string()],
+        )
+        .unwrap();
+        recipe.name.insert(Lang::Pt, "Panquecas".to_string());
+
+        assert_eq!(recipe.name_in(Lang::Pt), "Panquecas");
+        assert_eq!(recipe.name_in(Lang::En), "Pancakes");
+        // No Spanish translation: falls back to the English default.
+        assert_eq!(recipe.name_in(Lang::Es), "Pancakes");
+        assert_eq!(recipe.instructions_in(Lang::Es), &["Mix everything".to_string()]);
+    }
+
+    #[test]
+    fn should_localize_validation_errors_by_default_language() {
+        let err = Recipe::new(
+            "10".to_string(),
+            Lang::En,
+            "".to_string(),
+            vec!["breakfast".to_string()],
+            vec![Ingredient::new("flour".to_string(), 135.0, IngredientUnit::Gram)],
+            vec!["Mix everything".to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err, "A recipe needs a name");
+    }
+
+    #[test]
+    fn should_reject_a_name_payload_missing_its_default_translation() {
+        // `default` is Pt but only an English translation is provided: this
+        // must fail to deserialise rather than panic on later access.
+        let json = r#"{"default":"Pt","translations":{"En":"Pancakes"}}"#;
+        let result: Result<RecipeName, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_round_trip_a_valid_name_payload() {
+        let name = RecipeName::new(Lang::En, "Pancakes".to_string()).unwrap();
+        let json = serde_json::to_string(&name).unwrap();
+        let back: RecipeName = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, name);
+    }
+
+    fn component_recipe(id: &str, ingredients: Vec<Ingredient>) -> Recipe {
+        Recipe::new(
+            id.to_string(),
+            Lang::En,
+            id.to_string(),
+            vec!["component".to_string()],
+            ingredients,
+            vec!["Prepare".to_string()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_flatten_and_merge_component_ingredients() {
+        let bun = component_recipe(
+            "bun",
+            vec![Ingredient::new("flour".to_string(), 200.0, IngredientUnit::Gram)],
+        );
+        let patty = component_recipe(
+            "patty",
+            vec![Ingredient::new("beef".to_string(), 150.0, IngredientUnit::Gram)],
+        );
+        let book = RecipeBook::new(vec![bun, patty]);
+
+        let mut burger = component_recipe(
+            "burger",
+            vec![Ingredient::new("flour".to_string(), 20.0, IngredientUnit::Gram)],
+        );
+        burger.components = vec![
+            RecipeId::try_from("bun".to_string()).unwrap(),
+            RecipeId::try_from("patty".to_string()).unwrap(),
+        ];
+
+        let flattened = burger.flattened_ingredients(&book).unwrap();
+
+        // The burger's own flour and the bun's flour are summed.
+        let flour = flattened
+            .iter()
+            .find(|i| i.name == "flour" && i.unit == IngredientUnit::Gram)
+            .unwrap();
+        assert_eq!(flour.quantity, 220.0);
+        assert!(flattened
+            .iter()
+            .any(|i| i.name == "beef" && i.quantity == 150.0));
+    }
+
+    #[test]
+    fn should_sum_a_sub_recipe_shared_by_two_components() {
+        // Diamond: top → {left, right}, both → base (100g sugar). The shared
+        // base must contribute once per path, so sugar totals 200g.
+        let base = component_recipe(
+            "base",
+            vec![Ingredient::new("sugar".to_string(), 100.0, IngredientUnit::Gram)],
+        );
+        let mut left = component_recipe(
+            "left",
+            vec![Ingredient::new("butter".to_string(), 10.0, IngredientUnit::Gram)],
+        );
+        left.components = vec![RecipeId::try_from("base".to_string()).unwrap()];
+        let mut right = component_recipe(
+            "right",
+            vec![Ingredient::new("salt".to_string(), 1.0, IngredientUnit::Gram)],
+        );
+        right.components = vec![RecipeId::try_from("base".to_string()).unwrap()];
+        let book = RecipeBook::new(vec![base, left, right]);
+
+        let mut top = component_recipe(
+            "top",
+            vec![Ingredient::new("water".to_string(), 5.0, IngredientUnit::MilliLiter)],
+        );
+        top.components = vec![
+            RecipeId::try_from("left".to_string()).unwrap(),
+            RecipeId::try_from("right".to_string()).unwrap(),
+        ];
+
+        let flattened = top.flattened_ingredients(&book).unwrap();
+        let sugar = flattened
+            .iter()
+            .find(|i| i.name == "sugar" && i.unit == IngredientUnit::Gram)
+            .unwrap();
+        assert_eq!(sugar.quantity, 200.0);
+    }
+
+    #[test]
+    fn should_reject_unknown_component_references() {
+        let mut burger = component_recipe(
+            "burger",
+            vec![Ingredient::new("flour".to_string(), 20.0, IngredientUnit::Gram)],
+        );
+        burger.components = vec![RecipeId::try_from("missing".to_string()).unwrap()];
+        let book = RecipeBook::new(vec![]);
+
+        assert_eq!(
+            burger.flattened_ingredients(&book).unwrap_err(),
+            ResolveError::UnknownReference("missing".to_string())
+        );
+    }
+
+    #[test]
+    fn should_detect_component_cycles() {
+        let mut a = component_recipe(
+            "a",
+            vec![Ingredient::new("flour".to_string(), 1.0, IngredientUnit::Gram)],
+        );
+        a.components = vec![RecipeId::try_from("b".to_string()).unwrap()];
+        let mut b = component_recipe(
+            "b",
+            vec![Ingredient::new("water".to_string(), 1.0, IngredientUnit::MilliLiter)],
+        );
+        b.components = vec![RecipeId::try_from("a".to_string()).unwrap()];
+        let book = RecipeBook::new(vec![a.clone(), b]);
+
+        match a.flattened_ingredients(&book).unwrap_err() {
+            ResolveError::Cycle(path) => {
+                assert_eq!(path.first().map(String::as_str), Some("a"));
+                assert_eq!(path.last().map(String::as_str), Some("a"));
+            }
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+    }
 }